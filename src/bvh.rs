@@ -0,0 +1,97 @@
+use crate::{Fpr,Ray,Hit};
+use crate::geometry::{Aabb,RenderedBody};
+
+const MAX_LEAF_SIZE: usize = 4;
+
+#[derive(Clone)]
+enum BvhContents<'a> {
+    Leaf(Vec<&'a dyn RenderedBody<'a>>),
+    Branch(Box<BvhNode<'a>>,Box<BvhNode<'a>>),
+}
+
+#[derive(Clone)]
+struct BvhNode<'a> {
+    bbox: Aabb,
+    contents: BvhContents<'a>,
+}
+
+impl<'a> BvhNode<'a> {
+    fn build(mut bodies: Vec<&'a dyn RenderedBody<'a>>, axis: usize) -> Self {
+        if bodies.len() <= MAX_LEAF_SIZE {
+            let bbox = bodies.iter()
+                .map(|body| body.bounding_box())
+                .reduce(Aabb::surrounding)
+                .unwrap_or_else(Aabb::empty);
+
+            return BvhNode { bbox, contents: BvhContents::Leaf(bodies) };
+        }
+
+        bodies.sort_by(|a,b| {
+            let ac = a.bounding_box().centroid()[axis];
+            let bc = b.bounding_box().centroid()[axis];
+            ac.partial_cmp(&bc).unwrap()
+        });
+
+        let right_bodies = bodies.split_off(bodies.len()/2);
+        let next_axis = (axis + 1) % 3;
+        let left = BvhNode::build(bodies, next_axis);
+        let right = BvhNode::build(right_bodies, next_axis);
+        let bbox = Aabb::surrounding(left.bbox, right.bbox);
+
+        BvhNode {
+            bbox,
+            contents: BvhContents::Branch(Box::new(left),Box::new(right)),
+        }
+    }
+
+    fn get_hit(&self, ray: &Ray, tmin: Fpr, tmax: Fpr) -> Option<Hit> {
+        if !self.bbox.hit(ray,tmin,tmax) {
+            return None;
+        }
+
+        match &self.contents {
+            BvhContents::Leaf(bodies) => {
+                let mut closest = tmax;
+                let mut hit = None;
+
+                for body in bodies {
+                    if let Some(h) = body.get_hit(ray,Some(tmin),Some(closest)) {
+                        closest = h.t;
+                        hit = Some(h);
+                    }
+                }
+
+                hit
+            }
+            BvhContents::Branch(left,right) => {
+                let mut closest = tmax;
+                let mut hit = None;
+
+                if let Some(h) = left.get_hit(ray,tmin,closest) {
+                    closest = h.t;
+                    hit = Some(h);
+                }
+                if let Some(h) = right.get_hit(ray,tmin,closest) {
+                    hit = Some(h);
+                }
+
+                hit
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Bvh<'a> {
+    root: BvhNode<'a>,
+}
+
+impl<'a> Bvh<'a> {
+    pub(crate) fn build(bodies: Vec<&'a dyn RenderedBody<'a>>) -> Self {
+        Bvh { root: BvhNode::build(bodies,0) }
+    }
+
+    pub(crate) fn get_hit(&self, ray: &Ray, tmin: Fpr, tmax: Fpr) -> Option<Hit> {
+        self.root.get_hit(ray,tmin,tmax)
+    }
+}
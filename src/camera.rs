@@ -1,20 +1,15 @@
-use crate::{Fpr,Vector3,Ray};
+use rand::prelude::*;
 
-#[derive(Copy,Clone)]
-pub(crate) struct ViewPort {
-    aspect_ratio: Fpr,
-    width: Fpr,
-    height: Fpr,
-}
+use crate::{Fpr,Vector3,Ray};
 
-impl ViewPort {
-    pub(crate) fn new(aspect_ratio: Fpr, height: Fpr) -> Self {
-        let width = aspect_ratio * height;
-        
-        ViewPort {
-            aspect_ratio,
-            width,
-            height,
+fn random_in_unit_disk() -> Vector3 {
+    let mut rng = rand::thread_rng();
+    loop {
+        let x = rng.gen_range(-1.0..=1.0);
+        let y = rng.gen_range(-1.0..=1.0);
+        let vec = Vector3::new(x,y,0.0);
+        if vec.norm_squared() <= 1.0 {
+            return vec
         }
     }
 }
@@ -22,30 +17,55 @@ impl ViewPort {
 #[derive(Copy,Clone)]
 pub(crate) struct Camera {
     origin: Vector3,
-    focal_length: Fpr,
-    viewport: ViewPort,
+    lower_left_corner: Vector3,
     horizontal: Vector3,
     vertical: Vector3,
-    image_origin: Vector3,
+    u: Vector3,
+    v: Vector3,
+    lens_radius: Fpr,
+    time0: Fpr,
+    time1: Fpr,
 }
 
 impl Camera {
-    pub(crate) fn new(origin: Vector3, focal_length: Fpr, viewport: ViewPort, horizontal: Vector3, vertical: Vector3) -> Self {
-        let horizontal = horizontal.normalize() * viewport.width;
-        let vertical = vertical.normalize() * viewport.height;
-        let image_origin = origin - horizontal/2.0 - vertical/2.0 - Vector3::new(0.0,0.0,focal_length);
+    pub(crate) fn new(look_from: Vector3, look_at: Vector3, vup: Vector3, vfov: Fpr, aspect_ratio: Fpr, aperture: Fpr, focus_dist: Fpr, time0: Fpr, time1: Fpr) -> Self {
+        let theta = vfov.to_radians();
+        let viewport_height = 2.0 * (theta/2.0).tan();
+        let viewport_width = aspect_ratio * viewport_height;
+
+        let w = (look_from - look_at).normalize();
+        let u = vup.cross(&w).normalize();
+        let v = w.cross(&u);
+
+        let origin = look_from;
+        let horizontal = focus_dist * viewport_width * u;
+        let vertical = focus_dist * viewport_height * v;
+        let lower_left_corner = origin - horizontal/2.0 - vertical/2.0 - focus_dist*w;
 
         Camera {
             origin,
-            focal_length,
-            viewport,
+            lower_left_corner,
             horizontal,
             vertical,
-            image_origin,
+            u,
+            v,
+            lens_radius: aperture / 2.0,
+            time0,
+            time1,
         }
     }
 
-    pub(crate) fn get_ray(&self, u: Fpr, v: Fpr) -> Ray {
-        Ray::new(self.origin,self.image_origin + u*self.horizontal + v*self.vertical - self.origin)
+    pub(crate) fn get_ray(&self, s: Fpr, t: Fpr) -> Ray {
+        let rd = self.lens_radius * random_in_unit_disk();
+        let offset = self.u * rd.x + self.v * rd.y;
+
+        let mut rng = rand::thread_rng();
+        let time = rng.gen_range(self.time0..=self.time1);
+
+        Ray::new(
+            self.origin + offset,
+            self.lower_left_corner + s*self.horizontal + t*self.vertical - self.origin - offset,
+            time,
+        )
     }
 }
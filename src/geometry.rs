@@ -3,6 +3,58 @@ use crate::{Fpr,Vector3,Ray,Hit,Material};
 pub(crate) trait RenderedBody<'a> : Sync {
     fn get_hit(&'a self, ray: &Ray, tmin: Option<Fpr>, tmax: Option<Fpr>) -> Option<Hit>;
     fn get_material(&self) -> &'a dyn Material;
+    fn bounding_box(&self) -> Aabb;
+}
+
+#[derive(Copy,Clone)]
+pub(crate) struct Aabb {
+    pub(crate) min: Vector3,
+    pub(crate) max: Vector3,
+}
+
+impl Aabb {
+    pub(crate) fn new(min: Vector3, max: Vector3) -> Self {
+        Self { min, max }
+    }
+
+    pub(crate) fn empty() -> Self {
+        Self {
+            min: Vector3::new(Fpr::INFINITY,Fpr::INFINITY,Fpr::INFINITY),
+            max: Vector3::new(Fpr::NEG_INFINITY,Fpr::NEG_INFINITY,Fpr::NEG_INFINITY),
+        }
+    }
+
+    pub(crate) fn centroid(&self) -> Vector3 {
+        (self.min + self.max) / 2.0
+    }
+
+    pub(crate) fn surrounding(a: Aabb, b: Aabb) -> Aabb {
+        let min = Vector3::new(a.min.x.min(b.min.x), a.min.y.min(b.min.y), a.min.z.min(b.min.z));
+        let max = Vector3::new(a.max.x.max(b.max.x), a.max.y.max(b.max.y), a.max.z.max(b.max.z));
+        Aabb { min, max }
+    }
+
+    pub(crate) fn hit(&self, ray: &Ray, tmin: Fpr, tmax: Fpr) -> bool {
+        let mut tmin = tmin;
+        let mut tmax = tmax;
+
+        for axis in 0..3 {
+            let inv_d = 1.0 / ray.direction[axis];
+            let mut t0 = (self.min[axis] - ray.origin[axis]) * inv_d;
+            let mut t1 = (self.max[axis] - ray.origin[axis]) * inv_d;
+            if inv_d < 0.0 {
+                std::mem::swap(&mut t0,&mut t1);
+            }
+
+            tmin = if t0 > tmin { t0 } else { tmin };
+            tmax = if t1 < tmax { t1 } else { tmax };
+            if tmax <= tmin {
+                return false;
+            }
+        }
+
+        true
+    }
 }
 
 pub(crate) struct Sphere<'a> {
@@ -22,46 +74,102 @@ impl<'a> Sphere<'a> {
 
 }
 
-impl<'a> RenderedBody<'a> for Sphere<'a> {
-    fn get_hit(&self, ray: &Ray, tmin_opt: Option<Fpr>, tmax_opt: Option<Fpr>) -> Option<Hit> {
-        let oc = ray.origin - self.centre;
-        let a = ray.direction.dot(&ray.direction);
-        let half_b = oc.dot(&ray.direction);
-        let c = oc.dot(&oc) - self.radius.powi(2);
-        let discriminant = half_b.powi(2) - a*c;
+fn sphere_hit<'a>(ray: &Ray, centre: Vector3, radius: Fpr, material: &'a dyn Material, tmin_opt: Option<Fpr>, tmax_opt: Option<Fpr>) -> Option<Hit<'a>> {
+    let oc = ray.origin - centre;
+    let a = ray.direction.dot(&ray.direction);
+    let half_b = oc.dot(&ray.direction);
+    let c = oc.dot(&oc) - radius.powi(2);
+    let discriminant = half_b.powi(2) - a*c;
 
-        if discriminant < 0.0 {
-            return None;
-        }
+    if discriminant < 0.0 {
+        return None;
+    }
 
-        let tmin = tmin_opt.unwrap_or(0.0001);
-        let tmax = tmax_opt.unwrap_or(Fpr::INFINITY);
+    let tmin = tmin_opt.unwrap_or(0.0001);
+    let tmax = tmax_opt.unwrap_or(Fpr::INFINITY);
 
-        let dsqrt = discriminant.sqrt();
-        let mut t = (-half_b - dsqrt) / a;
+    let dsqrt = discriminant.sqrt();
+    let mut t = (-half_b - dsqrt) / a;
+    if t < tmin || t > tmax {
+        // Try other root
+        t = (-half_b + dsqrt) / a;
         if t < tmin || t > tmax {
-            // Try other root
-            t = (-half_b + dsqrt) / a;
-            if t < tmin || t > tmax { 
-                return None
-            }
+            return None
         }
+    }
 
-        let position = ray.at(t);
-        let normal = (position - self.centre).normalize();
-        Some( Hit {
-            t,
-            position,
-            normal,
-            material: self.get_material(),
-        })
-        
+    let position = ray.at(t);
+    let outward_normal = (position - centre).normalize();
+    let front_face = ray.direction.dot(&outward_normal) < 0.0;
+    let normal = if front_face { outward_normal } else { -outward_normal };
+
+    Some( Hit {
+        t,
+        position,
+        normal,
+        front_face,
+        material,
+    })
+}
+
+impl<'a> RenderedBody<'a> for Sphere<'a> {
+    fn get_hit(&self, ray: &Ray, tmin_opt: Option<Fpr>, tmax_opt: Option<Fpr>) -> Option<Hit> {
+        sphere_hit(ray, self.centre, self.radius, self.get_material(), tmin_opt, tmax_opt)
+    }
+
+    fn get_material(&self) -> &'a dyn Material {
+        self.material
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        let r = Vector3::new(self.radius,self.radius,self.radius);
+        Aabb::new(self.centre - r, self.centre + r)
+    }
+
+}
+
+pub(crate) struct MovingSphere<'a> {
+    centre0: Vector3,
+    centre1: Vector3,
+    time0: Fpr,
+    time1: Fpr,
+    radius: Fpr,
+    material: &'a dyn Material,
+}
+
+impl<'a> MovingSphere<'a> {
+	pub fn new(centre0: Vector3, centre1: Vector3, time0: Fpr, time1: Fpr, radius: Fpr, material: &'a dyn Material) -> Self {
+		Self {
+			centre0,
+			centre1,
+			time0,
+			time1,
+			radius,
+			material,
+		}
+	}
+
+    fn centre(&self, time: Fpr) -> Vector3 {
+        self.centre0 + ((time - self.time0) / (self.time1 - self.time0)) * (self.centre1 - self.centre0)
+    }
+}
+
+impl<'a> RenderedBody<'a> for MovingSphere<'a> {
+    fn get_hit(&self, ray: &Ray, tmin_opt: Option<Fpr>, tmax_opt: Option<Fpr>) -> Option<Hit> {
+        sphere_hit(ray, self.centre(ray.time), self.radius, self.get_material(), tmin_opt, tmax_opt)
     }
 
     fn get_material(&self) -> &'a dyn Material {
         self.material
     }
 
+    fn bounding_box(&self) -> Aabb {
+        let r = Vector3::new(self.radius,self.radius,self.radius);
+        let box0 = Aabb::new(self.centre(self.time0) - r, self.centre(self.time0) + r);
+        let box1 = Aabb::new(self.centre(self.time1) - r, self.centre(self.time1) + r);
+        Aabb::surrounding(box0,box1)
+    }
+
 }
 
 
@@ -115,10 +223,14 @@ impl<'a> RenderedBody<'a> for Plane<'a> {
 
         if 0.0 < x && x < self.extents[0] && 0.0 < y && y < self.extents[1] {
             // Within bounds
+            let front_face = ln < 0.0;
+            let normal = if front_face { self.normal } else { -self.normal };
+
             Some( Hit {
                 t,
                 position: p_intersect,
-                normal: self.normal,
+                normal,
+                front_face,
                 material: self.get_material(),
             })
         }
@@ -131,4 +243,112 @@ impl<'a> RenderedBody<'a> for Plane<'a> {
         self.material
     }
 
+    fn bounding_box(&self) -> Aabb {
+        const EPSILON: Fpr = 0.0001;
+
+        let corners = [
+            self.origin,
+            self.origin + self.x * self.extents[0],
+            self.origin + self.y * self.extents[1],
+            self.origin + self.x * self.extents[0] + self.y * self.extents[1],
+        ];
+
+        let mut min = corners[0];
+        let mut max = corners[0];
+        for corner in &corners[1..] {
+            min = Vector3::new(min.x.min(corner.x), min.y.min(corner.y), min.z.min(corner.z));
+            max = Vector3::new(max.x.max(corner.x), max.y.max(corner.y), max.z.max(corner.z));
+        }
+
+        let padding = Vector3::new(EPSILON,EPSILON,EPSILON);
+        Aabb::new(min - padding, max + padding)
+    }
+
+}
+
+pub(crate) struct Triangle<'a> {
+    v0: Vector3,
+    v1: Vector3,
+    v2: Vector3,
+    normals: Option<[Vector3;3]>,
+    material: &'a dyn Material,
+}
+
+impl<'a> Triangle<'a> {
+    pub fn new(v0: Vector3, v1: Vector3, v2: Vector3, normals: Option<[Vector3;3]>, material: &'a dyn Material) -> Self {
+        Self { v0, v1, v2, normals, material }
+    }
+}
+
+impl<'a> RenderedBody<'a> for Triangle<'a> {
+    fn get_hit(&self, ray: &Ray, tmin_opt: Option<Fpr>, tmax_opt: Option<Fpr>) -> Option<Hit> {
+        let e1 = self.v1 - self.v0;
+        let e2 = self.v2 - self.v0;
+        let p = ray.direction.cross(&e2);
+        let det = e1.dot(&p);
+
+        if det.abs() < 0.0001 {
+            // Ray parallel to the triangle's plane
+            return None;
+        }
+
+        let inv_det = det.recip();
+        let tvec = ray.origin - self.v0;
+        let u = tvec.dot(&p) * inv_det;
+        if u < 0.0 || u > 1.0 {
+            return None;
+        }
+
+        let q = tvec.cross(&e1);
+        let v = ray.direction.dot(&q) * inv_det;
+        if v < 0.0 || u+v > 1.0 {
+            return None;
+        }
+
+        let t = e2.dot(&q) * inv_det;
+
+        let tmin = tmin_opt.unwrap_or(0.0001);
+        let tmax = tmax_opt.unwrap_or(Fpr::INFINITY);
+        if t < tmin || t > tmax {
+            return None;
+        }
+
+        let outward_normal = match self.normals {
+            Some([n0,n1,n2]) => (1.0-u-v)*n0 + u*n1 + v*n2,
+            None => e1.cross(&e2),
+        }.normalize();
+
+        let front_face = ray.direction.dot(&outward_normal) < 0.0;
+        let normal = if front_face { outward_normal } else { -outward_normal };
+
+        Some( Hit {
+            t,
+            position: ray.at(t),
+            normal,
+            front_face,
+            material: self.get_material(),
+        })
+    }
+
+    fn get_material(&self) -> &'a dyn Material {
+        self.material
+    }
+
+    fn bounding_box(&self) -> Aabb {
+        const EPSILON: Fpr = 0.0001;
+
+        let min = Vector3::new(
+            self.v0.x.min(self.v1.x).min(self.v2.x),
+            self.v0.y.min(self.v1.y).min(self.v2.y),
+            self.v0.z.min(self.v1.z).min(self.v2.z),
+        );
+        let max = Vector3::new(
+            self.v0.x.max(self.v1.x).max(self.v2.x),
+            self.v0.y.max(self.v1.y).max(self.v2.y),
+            self.v0.z.max(self.v1.z).max(self.v2.z),
+        );
+
+        let padding = Vector3::new(EPSILON,EPSILON,EPSILON);
+        Aabb::new(min - padding, max + padding)
+    }
 }
@@ -0,0 +1,56 @@
+use crate::{Fpr,Vector3,Colour};
+
+pub(crate) trait Light : Sync {
+    fn sample_ray(&self, from: Vector3) -> (Vector3,Fpr,Colour);
+}
+
+pub(crate) struct PointLight {
+    position: Vector3,
+    intensity: Colour,
+}
+
+impl PointLight {
+    pub(crate) fn new(position: Vector3, intensity: Colour) -> Self {
+        Self { position, intensity }
+    }
+}
+
+impl Light for PointLight {
+    fn sample_ray(&self, from: Vector3) -> (Vector3,Fpr,Colour) {
+        let to_light = self.position - from;
+        let distance = to_light.norm();
+        let direction = to_light / distance;
+        let radiance = self.intensity / distance.powi(2);
+
+        (direction, distance, radiance)
+    }
+}
+
+pub(crate) struct SpotLight {
+    position: Vector3,
+    axis: Vector3,
+    cone_half_angle: Fpr,
+    intensity: Colour,
+}
+
+impl SpotLight {
+    pub(crate) fn new(position: Vector3, axis: Vector3, cone_half_angle: Fpr, intensity: Colour) -> Self {
+        Self { position, axis: axis.normalize(), cone_half_angle, intensity }
+    }
+}
+
+impl Light for SpotLight {
+    fn sample_ray(&self, from: Vector3) -> (Vector3,Fpr,Colour) {
+        let to_light = self.position - from;
+        let distance = to_light.norm();
+        let direction = to_light / distance;
+
+        let cos_angle = (-direction).dot(&self.axis);
+        let cos_cutoff = self.cone_half_angle.cos();
+        let spot_attenuation = if cos_angle > cos_cutoff { cos_angle } else { 0.0 };
+
+        let radiance = spot_attenuation * self.intensity / distance.powi(2);
+
+        (direction, distance, radiance)
+    }
+}
@@ -1,10 +1,8 @@
 use std::io;
 
-use rand::prelude::*;
-
 extern crate nalgebra as na;
 
-extern crate crossbeam;
+extern crate rayon;
 
 type Fpr = f64;
 
@@ -36,15 +34,16 @@ struct Ray {
     origin: Vector3,
     direction: Vector3,
     chroma: Chroma,
+    time: Fpr,
 }
 
 impl Ray {
-    fn new(origin: Vector3, direction: Vector3) -> Self {
-        Self { origin, direction, chroma: Chroma::White }
+    fn new(origin: Vector3, direction: Vector3, time: Fpr) -> Self {
+        Self { origin, direction, chroma: Chroma::White, time }
     }
 
-    fn new_chroma(origin: Vector3, direction: Vector3, chroma: Chroma) -> Self {
-        Self { origin, direction, chroma }
+    fn new_chroma(origin: Vector3, direction: Vector3, chroma: Chroma, time: Fpr) -> Self {
+        Self { origin, direction, chroma, time }
     }
 
     fn at(&self, t: Fpr) -> Vector3 {
@@ -56,6 +55,7 @@ struct Hit<'a> {
     t: Fpr,
     position: Vector3,
     normal: Vector3,
+    front_face: bool,
     material: &'a dyn Material,
 }
 
@@ -63,14 +63,23 @@ mod materials;
 use materials::{Material,Diffuse,Metal,Dielectric,DispersiveDielectric};
 
 mod camera;
-use camera::{Camera,ViewPort};
+use camera::Camera;
+
+mod lights;
+use lights::{Light,PointLight,SpotLight};
 
 mod geometry;
-use geometry::{Sphere,Plane,RenderedBody};
+use geometry::{Sphere,MovingSphere,Plane,RenderedBody};
+
+mod mesh;
+
+mod bvh;
 
 mod scene;
 use scene::{Scene};
 
+mod render;
+
 fn background_colour(ray: &Ray) -> Colour {
     let dirnorm = ray.direction.normalize();
     let t = 0.5 * (dirnorm.y + 1.0);
@@ -85,12 +94,28 @@ fn get_ray_colour(ray: &Ray, scene: &Scene, depth: u32) -> Colour {
 
     if let Some(hit) = scene.get_hit(ray,None,None) {
         let mut colour = Colour::zeros();
-        
+
+        if let Some(albedo) = hit.material.albedo() {
+            for light in &scene.lights {
+                let (light_dir,light_dist,radiance) = light.sample_ray(hit.position);
+                let ndotl = hit.normal.dot(&light_dir).max(0.0);
+
+                if ndotl > 0.0 {
+                    let shadow_ray = Ray::new(hit.position,light_dir,ray.time);
+                    let occluded = scene.get_hit(&shadow_ray,Some(0.0001),Some(light_dist-0.0001)).is_some();
+
+                    if !occluded {
+                        colour += radiance.component_mul(&albedo) * ndotl;
+                    }
+                }
+            }
+        }
+
         let scattered_rays = hit.material.response(ray,&hit);
         for (attenuation,scattered_ray) in scattered_rays {
             colour += get_ray_colour(&scattered_ray, scene, depth-1).component_mul(&attenuation)
         }
-        
+
         colour
     }
     else {
@@ -124,76 +149,39 @@ fn main() -> io::Result<()> {
     let world_sph = Sphere::new(Vector3::new(0.0,-100.5,-1.0), 100.0, &diffuse_ground);
     let mirror = Plane::new(Vector3::new(-1.8,-0.5,0.0),Vector3::new(0.0,0.0,-1.0),Vector3::new(0.0,1.0,0.0),[5.0,1.0],&dirty_metal);
 
+    const SHUTTER_OPEN: Fpr = 0.0;
+    const SHUTTER_CLOSE: Fpr = 1.0;
+    let bouncing_sph = MovingSphere::new(Vector3::new(-0.3,0.3,-0.8),Vector3::new(-0.3,0.6,-0.8),SHUTTER_OPEN,SHUTTER_CLOSE,0.15,&diffuse_blue);
+
+    let diffuse_orange = Diffuse::new(Colour::new(0.9,0.6,0.2));
+    let mesh_triangles = mesh::load_obj("assets/triangle.obj", &diffuse_orange)?;
+
+    // Lights
+    let key_light = PointLight::new(Vector3::new(2.0,2.0,1.0), Colour::new(20.0,20.0,20.0));
+    let fill_spot = SpotLight::new(Vector3::new(-1.5,1.5,0.5), Vector3::new(0.3,-1.0,-0.3), (30.0 as Fpr).to_radians(), Colour::new(15.0,15.0,15.0));
+
     // Camera configuration
-    const VIEWPORT_HEIGHT: Fpr = 2.0;
-    let viewport = ViewPort::new(ASPECT_RATIO,VIEWPORT_HEIGHT);
-    
-    const FOCAL_LENGTH: Fpr = 1.0;
-    const CAMERA_ORIGIN: Vector3 = Vector3::new(0.0,0.0,0.0);
-    const CAMERA_HORIZ: Vector3 = Vector3::new(1.0,0.0,0.0);
-    const CAMERA_VERT: Vector3 = Vector3::new(0.0,1.0,0.0);
-    let camera = Camera::new(CAMERA_ORIGIN,FOCAL_LENGTH,viewport,CAMERA_HORIZ,CAMERA_VERT);
-
-
-    let bodies: Vec<&dyn RenderedBody> = vec![
-        &centre_sph, &right_sph, &left_sph, &world_sph, &mirror
-    ]; 
-    let scene = Scene {
-        bodies,
-        camera
-    };
+    const VFOV: Fpr = 90.0;
+    const APERTURE: Fpr = 0.05;
+    let look_from = Vector3::new(0.0,0.0,0.0);
+    let look_at = Vector3::new(0.0,0.0,-1.0);
+    let vup = Vector3::new(0.0,1.0,0.0);
+    let focus_dist = (look_from - look_at).norm();
+    let camera = Camera::new(look_from,look_at,vup,VFOV,ASPECT_RATIO,APERTURE,focus_dist,SHUTTER_OPEN,SHUTTER_CLOSE);
 
-    // Actual rendering
-    let img = output::Image::new(IMAGE_WIDTH,IMAGE_HEIGHT);
-    
-    use std::sync::{Arc,Mutex};
-    let imgmutex = Arc::new(Mutex::new(img));
 
+    let mut bodies: Vec<&dyn RenderedBody> = vec![
+        &centre_sph, &right_sph, &left_sph, &world_sph, &mirror, &bouncing_sph
+    ];
+    bodies.extend(mesh_triangles.iter().map(|triangle| triangle as &dyn RenderedBody));
+
+    let lights: Vec<&dyn Light> = vec![&key_light, &fill_spot];
+    let scene = Scene::new(bodies, lights, camera);
+
+    // Actual rendering
     const PIXEL_SAMPLES: u32 = 256;
     const MAX_DEPTH: u32 = 16;
-    const NTHREADS: u32 = 8;
-
-    
-    let _ = crossbeam::thread::scope(|s| {
-        let mut threads = vec![];
-        
-        for t in 0..NTHREADS {
-            let scene = scene.clone();
-            let imgmutex = imgmutex.clone();
-            
-            let join_handle = s.spawn(move |_| {
-                let mut rng = rand::thread_rng();
-
-                for j in 0..IMAGE_HEIGHT {
-                    if j % NTHREADS != t { continue; }
-
-                    for i in 0..IMAGE_WIDTH {
-                            
-                        let mut colour = Colour::zeros();
-                        for _ in 0..PIXEL_SAMPLES {
-                            let image_x = i as Fpr + rng.gen::<Fpr>();
-                            let image_y = (IMAGE_HEIGHT-1-j) as Fpr + rng.gen::<Fpr>();
-
-                            let u = image_x / (IMAGE_WIDTH-1) as Fpr;
-                            let v = image_y / (IMAGE_HEIGHT-1) as Fpr;
-
-                            let ray = camera.get_ray(u, v);
-                            colour += get_ray_colour(&ray,&scene,MAX_DEPTH);
-                        }
-                        
-                        let mut img = imgmutex.lock().unwrap();
-                        img.set_pixel(i, j, output::PixelColour::from(colour/PIXEL_SAMPLES as Fpr).into() );
-                    }
-                }
-            });
-            threads.push(join_handle);
-        }
-
-        for thread in threads {
-            thread.join();
-        }
-    });
 
-    let img = imgmutex.lock().unwrap();
+    let img = render::render(&scene, IMAGE_WIDTH, IMAGE_HEIGHT, PIXEL_SAMPLES, MAX_DEPTH);
     img.save("img.bmp")
 }
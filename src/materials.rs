@@ -5,6 +5,12 @@ use crate::{Ray,Hit,Colour,Chroma};
 
 pub(crate) trait Material : Sync {
     fn response(&self, ray: &Ray, hit: &Hit) -> Vec<(Colour,Ray)>;
+
+    // Diffuse reflectance for direct light sampling; specular/refractive
+    // materials have no such term, so they keep the default of None.
+    fn albedo(&self) -> Option<Colour> {
+        None
+    }
 }
 
 pub(crate) struct Diffuse {
@@ -33,9 +39,13 @@ fn random_in_sphere() -> Vector3 {
 }
 
 impl Material for Diffuse {
-    fn response(&self, _ray: &Ray, hit: &Hit) -> Vec<(Colour,Ray)> {
+    fn response(&self, ray: &Ray, hit: &Hit) -> Vec<(Colour,Ray)> {
         let target = hit.position + hit.normal + random_in_sphere();
-        vec![(self.colour, Ray::new(hit.position,target-hit.position))]
+        vec![(self.colour, Ray::new(hit.position,target-hit.position,ray.time))]
+    }
+
+    fn albedo(&self) -> Option<Colour> {
+        Some(self.colour)
     }
 }
 
@@ -53,11 +63,10 @@ impl Metal {
 
 impl Material for Metal {
     fn response(&self, ray: &Ray, hit: &Hit) -> Vec<(Colour,Ray)> {
-        let raynorm = ray.direction.normalize();
-        let raydotnorm = raynorm.dot(&hit.normal);
-        if raydotnorm < 0.0 {
-            let reflected = raynorm - 2.0 * raydotnorm * hit.normal;
-            vec![(self.colour, Ray::new(hit.position,reflected))]
+        if hit.front_face {
+            let raynorm = ray.direction.normalize();
+            let reflected = raynorm - 2.0 * raynorm.dot(&hit.normal) * hit.normal;
+            vec![(self.colour, Ray::new(hit.position,reflected,ray.time))]
         }
         else {
             vec![]
@@ -89,38 +98,29 @@ impl Material for Dielectric {
 
         let raynorm = ray.direction.normalize();
 
-        let mut hnormal = hit.normal;
-        let mut raydotnorm = raynorm.dot(&hit.normal);
-
-        let refrac_ratio;
-        if raydotnorm > 0.0 { // Norm and ray in same direction
-            // Inside
-            hnormal = -hnormal;
-            raydotnorm = -raydotnorm;
-            refrac_ratio = self.refractive_index;
+        let refrac_ratio = if hit.front_face {
+            self.refractive_index.recip()
         } else {
-            // Outside
-            refrac_ratio = self.refractive_index.recip();
+            self.refractive_index
         };
 
-        let cos_theta = Fpr::min(-raydotnorm,1.0);
+        let cos_theta = Fpr::min(-raynorm.dot(&hit.normal),1.0);
         let sin_theta = (1.0 - cos_theta.powi(2)).sqrt();
 
         let cannot_refract = refrac_ratio * sin_theta > 1.0;
         if  cannot_refract || Self::reflectance(cos_theta,refrac_ratio) > rand::random() {
-            let raynorm = ray.direction.normalize();
             let reflected = raynorm - 2.0 * raynorm.dot(&hit.normal) * hit.normal;
-            vec![(self.colour, Ray::new(hit.position,reflected))]
+            vec![(self.colour, Ray::new(hit.position,reflected,ray.time))]
         }
         else {
-            let out_tang = refrac_ratio * (raynorm + cos_theta*hnormal);
-            let out_norm = -(1.0-out_tang.norm_squared()).abs().sqrt() * hnormal;
-    
+            let out_tang = refrac_ratio * (raynorm + cos_theta*hit.normal);
+            let out_norm = -(1.0-out_tang.norm_squared()).abs().sqrt() * hit.normal;
+
             let refracted = out_tang + out_norm;
-    
-            vec![(self.colour, Ray::new(hit.position,refracted))]
+
+            vec![(self.colour, Ray::new(hit.position,refracted,ray.time))]
         }
-        
+
     }
 }
 
@@ -148,24 +148,16 @@ impl Material for DispersiveDielectric {
 
         let raynorm = ray.direction.normalize();
 
-        let mut hnormal = hit.normal;
-        let mut raydotnorm = raynorm.dot(&hit.normal);
-
-        let refrac_ratios: [Fpr;3];
-        if raydotnorm > 0.0 { // Norm and ray in same direction
-            // Inside
-            hnormal = -hnormal;
-            raydotnorm = -raydotnorm;
-            refrac_ratios = self.refractive_indicies;
-        } else {
-            // Outside
-            refrac_ratios = [
+        let refrac_ratios: [Fpr;3] = if hit.front_face {
+            [
                 self.refractive_indicies[0].recip(),
                 self.refractive_indicies[1].recip(),
-                self.refractive_indicies[2].recip()];
+                self.refractive_indicies[2].recip()]
+        } else {
+            self.refractive_indicies
         };
 
-        let cos_theta = Fpr::min(-raydotnorm,1.0);
+        let cos_theta = Fpr::min(-raynorm.dot(&hit.normal),1.0);
         let sin_theta = (1.0 - cos_theta.powi(2)).sqrt();
 
         let chromas: Vec<usize> = match ray.chroma {
@@ -183,19 +175,18 @@ impl Material for DispersiveDielectric {
 
             let cannot_refract = refrac_ratios[c] * sin_theta > 1.0;
             if  cannot_refract || Self::reflectance(cos_theta,refrac_ratios[c]) > rand::random() {
-                let raynorm = ray.direction.normalize();
                 let reflected = raynorm - 2.0 * raynorm.dot(&hit.normal) * hit.normal;
-                (colour, Ray::new_chroma(hit.position,reflected,chroma))
+                (colour, Ray::new_chroma(hit.position,reflected,chroma,ray.time))
             }
             else {
-                let out_tang = refrac_ratios[c] * (raynorm + cos_theta*hnormal);
-                let out_norm = -(1.0-out_tang.norm_squared()).abs().sqrt() * hnormal;
-        
+                let out_tang = refrac_ratios[c] * (raynorm + cos_theta*hit.normal);
+                let out_norm = -(1.0-out_tang.norm_squared()).abs().sqrt() * hit.normal;
+
                 let refracted = out_tang + out_norm;
-        
-                (colour, Ray::new_chroma(hit.position,refracted,chroma))
+
+                (colour, Ray::new_chroma(hit.position,refracted,chroma,ray.time))
             }
         }).collect()
-        
+
     }
 }
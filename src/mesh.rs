@@ -0,0 +1,55 @@
+use std::fs;
+use std::io;
+
+use crate::{Fpr,Vector3,Material};
+use crate::geometry::Triangle;
+
+fn parse_face_vertex(token: &str) -> (usize,Option<usize>) {
+    let mut parts = token.split('/');
+    let v = parts.next().unwrap().parse::<usize>().unwrap() - 1;
+    let n = parts.nth(1).and_then(|s| s.parse::<usize>().ok()).map(|i| i - 1);
+    (v, n)
+}
+
+pub(crate) fn load_obj<'a>(path: &str, material: &'a dyn Material) -> io::Result<Vec<Triangle<'a>>> {
+    let contents = fs::read_to_string(path)?;
+
+    let mut vertices = Vec::new();
+    let mut normals = Vec::new();
+    let mut triangles = Vec::new();
+
+    for line in contents.lines() {
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => {
+                let c: Vec<Fpr> = tokens.map(|t| t.parse().unwrap()).collect();
+                vertices.push(Vector3::new(c[0],c[1],c[2]));
+            }
+            Some("vn") => {
+                let c: Vec<Fpr> = tokens.map(|t| t.parse().unwrap()).collect();
+                normals.push(Vector3::new(c[0],c[1],c[2]));
+            }
+            Some("f") => {
+                let refs: Vec<(usize,Option<usize>)> = tokens.map(parse_face_vertex).collect();
+
+                // Fan-triangulate faces with more than three vertices
+                for i in 1..refs.len().saturating_sub(1) {
+                    let (v0,n0) = refs[0];
+                    let (v1,n1) = refs[i];
+                    let (v2,n2) = refs[i+1];
+
+                    let tri_normals = match (n0,n1,n2) {
+                        (Some(a),Some(b),Some(c)) => Some([normals[a],normals[b],normals[c]]),
+                        _ => None,
+                    };
+
+                    triangles.push(Triangle::new(vertices[v0],vertices[v1],vertices[v2],tri_normals,material));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(triangles)
+}
@@ -0,0 +1,42 @@
+use rand::prelude::*;
+use rayon::prelude::*;
+
+use crate::{Fpr,Colour,Scene,get_ray_colour};
+use crate::output::{Image,PixelColour};
+
+pub(crate) fn render(scene: &Scene, width: u32, height: u32, samples: u32, max_depth: u32) -> Image {
+    let mut img = Image::new(width,height);
+
+    let rows: Vec<Vec<Colour>> = (0..height).into_par_iter()
+        .map(|j| {
+            let mut rng = rand::thread_rng();
+            let mut row = Vec::with_capacity(width as usize);
+
+            for i in 0..width {
+                let mut colour = Colour::zeros();
+                for _ in 0..samples {
+                    let image_x = i as Fpr + rng.gen::<Fpr>();
+                    let image_y = (height-1-j) as Fpr + rng.gen::<Fpr>();
+
+                    let u = image_x / (width-1) as Fpr;
+                    let v = image_y / (height-1) as Fpr;
+
+                    let ray = scene.camera.get_ray(u,v);
+                    colour += get_ray_colour(&ray,scene,max_depth);
+                }
+
+                row.push(colour / samples as Fpr);
+            }
+
+            row
+        })
+        .collect();
+
+    for (j,row) in rows.into_iter().enumerate() {
+        for (i,colour) in row.into_iter().enumerate() {
+            img.set_pixel(i as u32, j as u32, PixelColour::from(colour).into());
+        }
+    }
+
+    img
+}
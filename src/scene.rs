@@ -2,25 +2,29 @@ use crate::{Fpr,Ray,Hit};
 
 use crate::geometry::RenderedBody;
 use crate::camera::Camera;
+use crate::bvh::Bvh;
+use crate::lights::Light;
 
 #[derive(Clone)]
 pub(crate) struct Scene<'a> {
-    pub bodies: Vec<&'a dyn RenderedBody<'a>>,
+    bvh: Bvh<'a>,
+    pub lights: Vec<&'a dyn Light>,
     pub camera: Camera,
 }
 
 impl<'a> Scene<'a> {
-    pub(crate) fn get_hit(&self, ray: &Ray, tmin: Option<Fpr>, tmax: Option<Fpr>) -> Option<Hit> {
-        let mut closest = tmax.unwrap_or(Fpr::INFINITY);
-        let mut hit = None;
-
-        for hittable in &self.bodies {
-            if let Some(h) = hittable.get_hit(ray,tmin,Some(closest)) {
-                closest = h.t;
-                hit = Some(h);
-            }
+    pub(crate) fn new(bodies: Vec<&'a dyn RenderedBody<'a>>, lights: Vec<&'a dyn Light>, camera: Camera) -> Self {
+        Scene {
+            bvh: Bvh::build(bodies),
+            lights,
+            camera,
         }
+    }
+
+    pub(crate) fn get_hit(&self, ray: &Ray, tmin: Option<Fpr>, tmax: Option<Fpr>) -> Option<Hit> {
+        let tmin = tmin.unwrap_or(0.0001);
+        let tmax = tmax.unwrap_or(Fpr::INFINITY);
 
-        hit
+        self.bvh.get_hit(ray,tmin,tmax)
     }
 }